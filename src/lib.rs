@@ -4,20 +4,353 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::{cmp::Ordering, path::Path, process::Command};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    path::Path,
+    process::Command,
+    time::{Duration, Instant},
+};
 
 use anyhow::{anyhow, bail, Context, Result};
-use chessie::{perft, Game, Move};
+use chessie::{Game, Move, MoveKind, PieceKind, Square};
+use clap::ValueEnum;
+use rayon::prelude::*;
+use serde::Serialize;
+
+/// Controls how `autoperft` reports its results.
+///
+/// `Json` and `Tap` exist so a CI pipeline can parse results programmatically instead of
+/// scraping prose.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Prose, printed as the suites are checked (the default).
+    #[default]
+    Human,
+    /// One JSON object per line: suite index, FEN, depth, node counts, pass/fail, and (on
+    /// failure) the offending move, applied move line, and resulting FEN.
+    Json,
+    /// TAP (Test Anything Protocol) `ok`/`not ok` lines, with the failure detail as a YAML block.
+    Tap,
+}
+
+/// One structured result for a single `perft(depth)` check against one EPD suite.
+#[derive(Serialize)]
+struct CheckReport {
+    suite_index: usize,
+    fen: String,
+    depth: usize,
+    expected_nodes: u64,
+    actual_nodes: u64,
+    passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure: Option<MismatchDetail>,
+}
+
+/// The failure-specific fields of a [`CheckReport`], populated only when `passed` is `false`.
+#[derive(Serialize)]
+struct MismatchDetail {
+    offending_move: Option<String>,
+    applied_moves: String,
+    resulting_fen: String,
+    reason: String,
+}
+
+/// Routes structured [`CheckReport`]s to the user according to the chosen [`OutputFormat`].
+trait Reporter {
+    /// Reports the outcome of a single (suite, depth) check.
+    fn report(&mut self, report: &CheckReport);
+
+    /// Called once when the run is over (after every suite, or after the failing suite that
+    /// aborted the run), for formats with a trailing summary
+    /// (e.g. TAP's `1..N` plan line). A no-op by default.
+    fn finish(&mut self) {}
+}
+
+/// Prints prose, matching autoperft's traditional (pre-`--format`) output.
+///
+/// All of that prose is printed elsewhere, while the check runs (progress lines) or by `main`
+/// (the final error), so there is nothing left for this reporter to do.
+struct HumanReporter;
+
+impl Reporter for HumanReporter {
+    fn report(&mut self, _report: &CheckReport) {}
+}
+
+/// Emits one JSON object per line (JSON Lines), one per (suite, depth) check.
+struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn report(&mut self, report: &CheckReport) {
+        match serde_json::to_string(report) {
+            Ok(line) => println!("{line}"),
+            Err(e) => eprintln!("Failed to serialize check report to JSON: {e}"),
+        }
+    }
+}
+
+/// Emits TAP output: one `ok`/`not ok` line per check, with failure detail as a YAML diagnostic
+/// block, followed by a trailing `1..N` plan line once the run is over.
+#[derive(Default)]
+struct TapReporter {
+    count: usize,
+}
+
+impl Reporter for TapReporter {
+    fn report(&mut self, report: &CheckReport) {
+        self.count += 1;
+        let description = format!(
+            "suite {} perft({}) on {:?}",
+            report.suite_index, report.depth, report.fen
+        );
+
+        if report.passed {
+            println!("ok {} - {description}", self.count);
+            return;
+        }
+
+        println!("not ok {} - {description}", self.count);
+        println!("  ---");
+        println!("  expected_nodes: {}", report.expected_nodes);
+        println!("  actual_nodes: {}", report.actual_nodes);
+        if let Some(failure) = &report.failure {
+            if let Some(mv) = &failure.offending_move {
+                println!("  offending_move: {mv}");
+            }
+            println!("  applied_moves: {}", failure.applied_moves);
+            println!("  resulting_fen: {:?}", failure.resulting_fen);
+            println!("  reason: {:?}", failure.reason);
+        }
+        println!("  ...");
+    }
+
+    fn finish(&mut self) {
+        println!("1..{}", self.count);
+    }
+}
+
+/// Caches the node count of a (position, remaining depth) pair, so that transpositions
+/// encountered while bisecting a failing line aren't re-expanded from scratch.
+///
+/// The table is keyed by `(zobrist hash, depth)` rather than just the hash, since the
+/// number of nodes reachable from a position depends on how many plies remain.
+type PerftTable = HashMap<(u64, usize), u64>;
+
+/// One suite's worth of perft tests: a FEN and the `(depth, expected_nodes)` pairs to check it
+/// against, as parsed from an EPD line or a single position visited while walking a PGN's
+/// mainline.
+type Suite = (String, Vec<(usize, u64)>);
+
+/// A [`Suite`] paired with its 1-based position in the input, or `None` for an EPD line that
+/// failed to parse under `--keep-going` (already recorded as a [`RunFailure::Parse`]).
+type IndexedSuite = Option<(usize, Suite)>;
+
+/// A minimal named stopwatch, used by `--benchmark` mode to time sections of a perft check.
+#[derive(Default)]
+struct Timers {
+    running: HashMap<&'static str, Instant>,
+}
+
+impl Timers {
+    /// Starts (or restarts) the named timer.
+    fn start(&mut self, name: &'static str) {
+        self.running.insert(name, Instant::now());
+    }
+
+    /// Stops the named timer, returning the elapsed time since it was started.
+    ///
+    /// # Panics
+    /// Panics if `name` was never started.
+    fn stop(&mut self, name: &'static str) -> Duration {
+        self.running
+            .remove(name)
+            .unwrap_or_else(|| panic!("timer {name:?} was stopped before it was started"))
+            .elapsed()
+    }
+}
+
+/// Node count and wall-clock time for one `perft(depth)` check, or summed over several via
+/// [`Self::add`], for both the user script and the reference generator, used to derive
+/// nodes-per-second.
+#[derive(Default, Clone, Copy)]
+struct BenchmarkTotals {
+    user_nodes: u64,
+    user_time: Duration,
+    reference_nodes: u64,
+    reference_time: Duration,
+}
+
+impl BenchmarkTotals {
+    /// Folds another measurement into this running total.
+    fn add(&mut self, other: &BenchmarkTotals) {
+        self.user_nodes += other.user_nodes;
+        self.user_time += other.user_time;
+        self.reference_nodes += other.reference_nodes;
+        self.reference_time += other.reference_time;
+    }
+
+    fn user_nps(&self) -> f64 {
+        nodes_per_second(self.user_nodes, self.user_time)
+    }
+
+    fn reference_nps(&self) -> f64 {
+        nodes_per_second(self.reference_nodes, self.reference_time)
+    }
+
+    /// Prints a single summary line comparing the user script to the reference generator.
+    fn print_line(&self, label: &str) {
+        println!(
+            "\t[{label}] user: {} nodes in {:.3?} ({:.0} nps) | reference: {} nodes in {:.3?} ({:.0} nps)",
+            self.user_nodes,
+            self.user_time,
+            self.user_nps(),
+            self.reference_nodes,
+            self.reference_time,
+            self.reference_nps(),
+        );
+    }
+}
+
+/// Computes nodes-per-second, treating an immeasurably small duration as `0`.
+fn nodes_per_second(nodes: u64, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        0.0
+    } else {
+        nodes as f64 / elapsed.as_secs_f64()
+    }
+}
+
+/// Everything produced by checking one suite's (depth, expected) pairs, computed independently
+/// of every other suite so `--jobs N` can run many of these concurrently before anything is
+/// printed. See [`PerftChecker::process_suite`] and [`PerftChecker::report_suite`].
+struct SuiteOutcome {
+    fen: String,
+    /// One report per (depth, expected) pair checked, in order — including a failing one under
+    /// `fatal`, so it still gets reported before the run aborts.
+    reports: Vec<CheckReport>,
+    totals: BenchmarkTotals,
+    failures: Vec<SuiteFailure>,
+    /// Set when a check failed without `--keep-going`: the error that should abort the run,
+    /// once its (already-built) failing [`CheckReport`] has been reported.
+    fatal: Option<anyhow::Error>,
+}
+
+/// Returned by a successful [`PerftChecker::check_splitperft`] call.
+struct CheckOutcome {
+    /// Total nodes the user script reported for this (FEN, depth) pair.
+    actual_nodes: u64,
+    /// Benchmark measurement for this check, present only under `--benchmark`.
+    benchmark: Option<BenchmarkTotals>,
+}
+
+/// A move-generation discrepancy discovered while checking (and, if necessary, bisecting)
+/// a failing perft check. Carries enough detail for a `--format json`/`tap` reporter to
+/// describe exactly what went wrong, without re-parsing a prose error message.
+#[derive(Debug)]
+struct Mismatch {
+    /// Total nodes the user script reported for the *top-level* (FEN, depth) being checked,
+    /// not whatever shallower position bisection ultimately blamed.
+    actual_nodes: u64,
+    /// The single move blamed for the discrepancy, if bisection narrowed it down to one.
+    offending_move: Option<String>,
+    /// Every move applied, in order, to reach the blamed position.
+    applied_moves: Vec<String>,
+    /// FEN of the position after `applied_moves` was applied to the suite's starting FEN.
+    resulting_fen: String,
+    /// Short human-readable explanation of the discrepancy.
+    reason: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason)?;
+        if !self.applied_moves.is_empty() {
+            write!(f, "\nApplied moves: {}", self.applied_moves.join(", "))?;
+        }
+        write!(f, "\nResulting FEN: {:?}", self.resulting_fen)
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// A single (suite, depth) check that failed under `--keep-going`, recorded for the final
+/// aggregated summary instead of aborting the run immediately.
+struct SuiteFailure {
+    fen: String,
+    depth: usize,
+    /// `Display` text of the error that `check_splitperft` returned (a [`Mismatch`] in the
+    /// common case), preserved verbatim so the summary matches what `--format human` would
+    /// have printed had the run stopped here.
+    error: String,
+}
+
+impl fmt::Display for SuiteFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Suite {:?}, perft({}):", self.fen, self.depth)?;
+        write!(f, "{}", self.error)
+    }
+}
+
+/// One failure recorded during a `--keep-going` run: a suite that failed its perft check, an
+/// EPD line that couldn't be parsed at all, or a PGN game that couldn't be walked at all.
+enum RunFailure {
+    Suite(SuiteFailure),
+    Parse { line_number: usize, reason: String },
+    PgnGame { game_index: usize, reason: String },
+}
+
+impl fmt::Display for RunFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Suite(failure) => write!(f, "{failure}"),
+            Self::Parse { line_number, reason } => {
+                write!(f, "Line {line_number}: malformed EPD record ({reason})")
+            }
+            Self::PgnGame { game_index, reason } => {
+                write!(f, "Game {game_index}: {reason}")
+            }
+        }
+    }
+}
 
 /// Encapsulates frequently-used data like the user-supplied script
 pub struct PerftChecker<'a> {
     user_script: &'a str,
+
+    /// When `true`, times the user script against the reference generator and reports
+    /// nodes-per-second for both.
+    benchmark: bool,
+
+    /// Controls how check results are reported to the user.
+    format: OutputFormat,
+
+    /// When `true`, a failing suite or malformed EPD line is recorded rather than aborting
+    /// the run, so every suite in the file gets checked and reported on in one pass.
+    keep_going: bool,
+
+    /// Number of worker threads to check suites on, and to divide each reference `perft`'s
+    /// root-level moves across. `1` runs everything on the calling thread, exactly as before
+    /// `--jobs` existed.
+    jobs: usize,
 }
 
 impl<'a> PerftChecker<'a> {
     /// Create the checker with the user-supplied script
-    pub fn new(user_script: &'a str) -> Self {
-        Self { user_script }
+    pub fn new(
+        user_script: &'a str,
+        benchmark: bool,
+        format: OutputFormat,
+        keep_going: bool,
+        jobs: usize,
+    ) -> Self {
+        Self {
+            user_script,
+            benchmark,
+            format,
+            keep_going,
+            jobs,
+        }
     }
 
     /// Runs the checker on the provided EPD file.
@@ -33,28 +366,298 @@ impl<'a> PerftChecker<'a> {
         let epd_tests = Vec::from(&contents.lines().collect::<Vec<_>>()[start_index..end_index]);
         let num_tests = epd_tests.len();
 
-        // Run each individual test suite
+        // Every suite failure and malformed EPD line gets collected here under `--keep-going`;
+        // otherwise the run aborts via `?` the moment the first one occurs and this stays empty.
+        let mut failures = Vec::new();
+
+        // Parse every EPD line up front: `check_splitperft` only needs owned data from here on,
+        // which is what lets `--jobs` hand each suite to a worker thread below.
+        let mut suites = Vec::with_capacity(num_tests);
         for (i, epd) in epd_tests.into_iter().enumerate() {
-            let (fen, tests) = self.parse_epd(epd)?;
+            match self.parse_epd(epd) {
+                Ok((fen, tests)) => suites.push(Some((i + 1, (fen.to_string(), tests)))),
+                Err(e) if self.keep_going => {
+                    failures.push(RunFailure::Parse {
+                        line_number: start_index + i + 1,
+                        reason: e.to_string(),
+                    });
+                    suites.push(None);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.run_suites(suites, num_tests, failures)
+    }
+
+    /// Runs the checker on every position visited while walking the mainline of each game in a
+    /// PGN file, checking each one with a single `perft(depth)`.
+    ///
+    /// A game's starting position is the standard one, unless it has a `[FEN "..."]` tag.
+    pub fn run_pgn(&self, pgn_file: impl AsRef<Path>, depth: usize) -> Result<()> {
+        let contents = std::fs::read_to_string(pgn_file)?;
+        let mut failures = Vec::new();
+        let suites = self.parse_pgn(&contents, depth, &mut failures)?;
+        let num_tests = suites.len();
+
+        let suites = suites
+            .into_iter()
+            .enumerate()
+            .map(|(i, suite)| Some((i + 1, suite)))
+            .collect();
+
+        self.run_suites(suites, num_tests, failures)
+    }
+
+    /// Checks every parsed suite (a `None` marks an EPD line that failed to parse under
+    /// `--keep-going`, already recorded in `failures`) and reports the results in input order.
+    ///
+    /// Under `--jobs N` (`N > 1`), suites are checked across a pool of `N` worker threads; the
+    /// results are still collected into a plain `Vec` and replayed in order afterwards, so
+    /// output is identical to a sequential run regardless of which worker finishes first.
+    ///
+    /// Without `--keep-going`, a sequential run (`--jobs 1`) still stops at the first failing
+    /// suite, skipping every later one. Under `--jobs N` the workers are already running
+    /// concurrently, so every suite's child process runs to completion before the first error is
+    /// seen here; only the remaining *reporting* is skipped once it's found. Either way, the
+    /// failing suite's own report is always reported before the run aborts.
+    fn run_suites(
+        &self,
+        suites: Vec<IndexedSuite>,
+        num_tests: usize,
+        mut failures: Vec<RunFailure>,
+    ) -> Result<()> {
+        let check = |entry: IndexedSuite| {
+            entry.map(|(suite_index, (fen, tests))| {
+                let outcome = self.process_suite(suite_index, &fen, tests);
+                (suite_index, outcome)
+            })
+        };
+
+        let outcomes: Box<dyn Iterator<Item = Option<(usize, SuiteOutcome)>> + '_> =
+            if self.jobs > 1 {
+                let pool = rayon::ThreadPoolBuilder::new().num_threads(self.jobs).build()?;
+                let collected: Vec<_> =
+                    pool.install(|| suites.into_par_iter().map(check).collect());
+                Box::new(collected.into_iter())
+            } else {
+                // Lazily, so each suite is only checked once the previous one has been reported,
+                // and the `break` below leaves every suite after a fatal failure unchecked.
+                Box::new(suites.into_iter().map(check))
+            };
+
+        let mut reporter = self.make_reporter();
+        let mut suite_totals = Vec::new();
+        let mut fatal = None;
+
+        for (suite_index, mut outcome) in outcomes.flatten() {
+            let err = outcome.fatal.take();
+            self.report_suite(
+                suite_index,
+                num_tests,
+                outcome,
+                reporter.as_mut(),
+                &mut suite_totals,
+                &mut failures,
+            );
+
+            if let Some(err) = err {
+                fatal = Some(err);
+                break;
+            }
+        }
+
+        self.finish_run(reporter, num_tests, suite_totals, failures)?;
+
+        match fatal {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Creates the [`Reporter`] matching this checker's [`OutputFormat`].
+    fn make_reporter(&self) -> Box<dyn Reporter> {
+        match self.format {
+            OutputFormat::Human => Box::new(HumanReporter),
+            OutputFormat::Json => Box::new(JsonReporter),
+            OutputFormat::Tap => Box::new(TapReporter::default()),
+        }
+    }
+
+    /// Prints a suite's buffered progress and reports under `--format human`/`json`/`tap`, and
+    /// folds its outcome into `suite_totals` and `failures`.
+    ///
+    /// Split out from [`Self::process_suite`] so that under `--jobs N` every suite can be
+    /// checked concurrently while still being printed in input order afterwards.
+    #[allow(clippy::too_many_arguments)]
+    fn report_suite(
+        &self,
+        suite_index: usize,
+        num_tests: usize,
+        outcome: SuiteOutcome,
+        reporter: &mut dyn Reporter,
+        suite_totals: &mut Vec<(String, BenchmarkTotals)>,
+        failures: &mut Vec<RunFailure>,
+    ) {
+        if self.format == OutputFormat::Human {
             println!(
-                "Beginning tests on perft suite {}/{num_tests}: {fen:?}",
-                i + 1
+                "Beginning tests on perft suite {suite_index}/{num_tests}: {:?}",
+                outcome.fen
+            );
+        }
+
+        for report in &outcome.reports {
+            if self.format == OutputFormat::Human {
+                println!("\tChecking perft({}) := {}", report.depth, report.expected_nodes);
+            }
+            reporter.report(report);
+        }
+
+        failures.extend(outcome.failures.into_iter().map(RunFailure::Suite));
+
+        if self.benchmark {
+            // The benchmark line is prose; under `--format json`/`tap` it would otherwise be
+            // interleaved with (or, for TAP, inserted before the plan line after) the structured
+            // records a CI pipeline is trying to parse.
+            if self.format == OutputFormat::Human {
+                outcome.totals.print_line(&outcome.fen);
+            }
+            suite_totals.push((outcome.fen, outcome.totals));
+        }
+    }
+
+    /// Flushes the reporter, prints the benchmark/failure summaries, and turns any collected
+    /// failures into the run's overall error.
+    ///
+    /// The summaries are prose, so (like [`Self::report_suite`]'s per-suite benchmark line)
+    /// they're only printed under `--format human`; `--format json`/`tap` rely entirely on the
+    /// structured records [`Reporter::report`] already emitted for every check.
+    fn finish_run(
+        &self,
+        mut reporter: Box<dyn Reporter>,
+        num_tests: usize,
+        suite_totals: Vec<(String, BenchmarkTotals)>,
+        failures: Vec<RunFailure>,
+    ) -> Result<()> {
+        reporter.finish();
+
+        if self.benchmark && self.format == OutputFormat::Human {
+            self.print_benchmark_summary(&suite_totals);
+        }
+
+        if !failures.is_empty() {
+            if self.format == OutputFormat::Human {
+                self.print_failure_summary(&failures);
+            }
+            // `failures` has one entry per failing (suite, depth) check, malformed EPD line, or
+            // PGN game that couldn't be walked, not one per suite (a suite failing at multiple depths pushes multiple entries), so
+            // it's reported as "checks", not "suites".
+            bail!(
+                "{} check(s) failed out of {num_tests} suite(s) checked (see summary above)",
+                failures.len()
             );
-            self.check_epd(fen, tests)?;
         }
 
         Ok(())
     }
 
-    /// Checks that all of the PERFT results on the provided `epd` string are valid.
-    fn check_epd(&self, fen: &str, tests: Vec<(usize, u64)>) -> Result<()> {
+    /// Prints every failure collected during a `--keep-going` run.
+    fn print_failure_summary(&self, failures: &[RunFailure]) {
+        println!("\n=== {} failure(s) ===", failures.len());
+        for failure in failures {
+            println!("\n{failure}");
+        }
+    }
+
+    /// Prints the final nodes-per-second summary table across every suite that was run.
+    fn print_benchmark_summary(&self, suite_totals: &[(String, BenchmarkTotals)]) {
+        println!("\n=== Benchmark summary ===");
+        let mut grand_total = BenchmarkTotals::default();
+        for (fen, totals) in suite_totals {
+            totals.print_line(fen);
+            grand_total.add(totals);
+        }
+        grand_total.print_line("TOTAL");
+    }
+
+    /// Checks that all of the PERFT results for one suite are valid, computing every
+    /// (depth, expected) check but not printing or reporting any of them.
+    ///
+    /// Kept free of *reporting* side effects so that under `--jobs N` many suites can run this
+    /// concurrently on a thread pool; [`Self::report_suite`] does the final, in-order printing
+    /// afterwards. [`Self::check_splitperft`]'s own bisection diagnostics are still written to
+    /// stderr as they're discovered, though, so they may interleave across workers under `--jobs`
+    /// — they're debug tracing, not part of the ordered report.
+    ///
+    /// Under `--keep-going`, a failing depth is pushed onto the outcome's failures and checking
+    /// continues with the suite's remaining depths. Otherwise, checking this suite stops at the
+    /// first failing depth and the error is returned via the outcome's `fatal` field — its
+    /// failing [`CheckReport`] is still included in `reports`, so [`Self::run_suites`] can report
+    /// it before propagating `fatal` and aborting the run.
+    fn process_suite(&self, suite_index: usize, fen: &str, tests: Vec<(usize, u64)>) -> SuiteOutcome {
+        let mut reports = Vec::with_capacity(tests.len());
+        let mut totals = BenchmarkTotals::default();
+        let mut failures = Vec::new();
+        let mut fatal = None;
+        // Scope a fresh table to this suite, so memoized node counts don't pile up across suites
+        let mut tt = PerftTable::new();
+
         for (depth, expected) in tests {
-            println!("\tChecking perft({depth}) := {expected}");
             // Check if the user-supplied move generator is correct for this depth and FEN
-            self.check_splitperft::<false>(depth, fen, &[])?;
+            match self.check_splitperft::<false>(depth, fen, &[], &mut tt) {
+                Ok(outcome) => {
+                    if let Some(sample) = outcome.benchmark {
+                        totals.add(&sample);
+                    }
+                    reports.push(CheckReport {
+                        suite_index,
+                        fen: fen.to_string(),
+                        depth,
+                        expected_nodes: expected,
+                        actual_nodes: outcome.actual_nodes,
+                        passed: true,
+                        failure: None,
+                    });
+                }
+                Err(err) => {
+                    let mismatch = err.downcast_ref::<Mismatch>();
+                    reports.push(CheckReport {
+                        suite_index,
+                        fen: fen.to_string(),
+                        depth,
+                        expected_nodes: expected,
+                        actual_nodes: mismatch.map_or(0, |m| m.actual_nodes),
+                        passed: false,
+                        failure: mismatch.map(|m| MismatchDetail {
+                            offending_move: m.offending_move.clone(),
+                            applied_moves: m.applied_moves.join(", "),
+                            resulting_fen: m.resulting_fen.clone(),
+                            reason: m.reason.clone(),
+                        }),
+                    });
+
+                    if self.keep_going {
+                        failures.push(SuiteFailure {
+                            fen: fen.to_string(),
+                            depth,
+                            error: err.to_string(),
+                        });
+                        continue;
+                    }
+
+                    fatal = Some(err);
+                    break;
+                }
+            }
         }
 
-        Ok(())
+        SuiteOutcome {
+            fen: fen.to_string(),
+            reports,
+            totals,
+            failures,
+            fatal,
+        }
     }
 
     /// Executes the user-supplied splitperft script, returning it's `stdout`.
@@ -129,13 +732,21 @@ impl<'a> PerftChecker<'a> {
     /// Generates a (correct) splitperft.
     ///
     /// For each legal move, it generates the possible nodes reachable from playing that move.
+    ///
+    /// Under `--jobs N` (`N > 1`), the root-level moves are divided across a thread pool instead
+    /// of walked one at a time: each root move's subtree is counted with its own fresh
+    /// [`PerftTable`], since the table isn't
+    /// safe to share without synchronization and the root divide already splits the search space
+    /// into independent subtrees that rarely transpose into each other anyway. With `--jobs 1`,
+    /// the walk is sequential and shares `tt` with the rest of the bisection, exactly as before
+    /// `--jobs` existed.
     fn generate_splitperft(
         &self,
         depth: usize,
         fen: &str,
         moves: &[String],
+        tt: &mut PerftTable,
     ) -> (Vec<(String, u64)>, u64) {
-        let mut results = Vec::with_capacity(128);
         let mut board = Game::from_fen(fen).unwrap();
 
         // If there were any moves supplied, apply them
@@ -146,11 +757,28 @@ impl<'a> PerftChecker<'a> {
             };
         }
 
+        let legal_moves: Vec<Move> = board.get_legal_moves().into_iter().collect();
+
+        if self.jobs > 1 {
+            let results: Vec<(String, u64)> = legal_moves
+                .into_par_iter()
+                .map(|mv| {
+                    let new_board = board.with_move_made(mv);
+                    let new_nodes = perft_with_tt(&new_board, depth - 1, &mut PerftTable::new());
+                    (mv.to_string(), new_nodes)
+                })
+                .collect();
+
+            let nodes = results.iter().map(|(_, n)| n).sum();
+            return (results, nodes);
+        }
+
+        let mut results = Vec::with_capacity(128);
         let mut nodes = 0;
-        for mv in board.get_legal_moves() {
+        for mv in legal_moves {
             let new_board = board.with_move_made(mv);
 
-            let new_nodes = perft(&new_board, depth - 1);
+            let new_nodes = perft_with_tt(&new_board, depth - 1, tt);
             nodes += new_nodes;
 
             results.push((mv.to_string(), new_nodes));
@@ -198,6 +826,62 @@ impl<'a> PerftChecker<'a> {
         Ok((fen, tests))
     }
 
+    /// Parses every game in a PGN file's contents, walking each game's mainline and emitting
+    /// one suite per position visited (including the starting position, before any moves are
+    /// made), each checked with a single `perft(depth)`.
+    ///
+    /// A game's starting position is the standard one, unless it has a `[FEN "..."]` tag.
+    ///
+    /// Under `--keep-going`, a game with an invalid `[FEN]` tag or an unparseable SAN move is
+    /// recorded into `failures` and the rest of that game is skipped (positions reached before
+    /// the bad move are still checked), rather than aborting the rest of the file; this
+    /// mirrors how [`Self::parse_epd`] failures are handled for `--epd` input.
+    fn parse_pgn(&self, contents: &str, depth: usize, failures: &mut Vec<RunFailure>) -> Result<Vec<Suite>> {
+        let mut suites = Vec::new();
+
+        'games: for (game_index, game_text) in split_pgn_games(contents).iter().enumerate() {
+            let mut game = match extract_pgn_tag(game_text, "FEN") {
+                Some(fen) => match Game::from_fen(&fen)
+                    .context(format!("invalid [FEN] tag {fen:?}"))
+                {
+                    Ok(game) => game,
+                    Err(e) if self.keep_going => {
+                        failures.push(RunFailure::PgnGame {
+                            game_index: game_index + 1,
+                            reason: e.to_string(),
+                        });
+                        continue 'games;
+                    }
+                    Err(e) => return Err(e),
+                },
+                None => Game::default(),
+            };
+
+            suites.push(perft_suite(&game, depth));
+
+            for mv_str in mainline_sans(game_text) {
+                let mv = match move_from_san(&game, &mv_str).context(format!(
+                    "failed to parse SAN move {mv_str:?} on {:?}",
+                    game.to_fen()
+                )) {
+                    Ok(mv) => mv,
+                    Err(e) if self.keep_going => {
+                        failures.push(RunFailure::PgnGame {
+                            game_index: game_index + 1,
+                            reason: e.to_string(),
+                        });
+                        continue 'games;
+                    }
+                    Err(e) => return Err(e),
+                };
+                game = game.with_move_made(mv);
+                suites.push(perft_suite(&game, depth));
+            }
+        }
+
+        Ok(suites)
+    }
+
     /// Check if the user-supplied script generated the correct splitperft results on the provided position.
     ///
     /// If not, recursive down the line of illegal moves until the "problematic" position is found.
@@ -207,14 +891,30 @@ impl<'a> PerftChecker<'a> {
         depth: usize,
         fen: &str,
         moves: &[String],
-    ) -> Result<()> {
+        tt: &mut PerftTable,
+    ) -> Result<CheckOutcome> {
+        let mut timers = Timers::default();
+
         // Get the perft results from the user-supplied script
+        timers.start("user_script");
         let user_output = self.exec_user_perft(depth, fen, moves)?;
+        let user_time = timers.stop("user_script");
         // We only need the total node count for now
         let user_nodes = self.parse_splitperft_output_nodes_only(&user_output)?;
 
         // Generate the correct result
-        let (correct_splitperft, correct_nodes) = self.generate_splitperft(depth, fen, moves);
+        timers.start("reference");
+        let (correct_splitperft, correct_nodes) = self.generate_splitperft(depth, fen, moves, tt);
+        let reference_time = timers.stop("reference");
+
+        // Bisection re-checks an already-failing line at shallower depths, so only the
+        // top-level, non-bisecting check is representative of the generator's real speed.
+        let benchmark = (self.benchmark && !ILLEGAL).then_some(BenchmarkTotals {
+            user_nodes,
+            user_time,
+            reference_nodes: correct_nodes,
+            reference_time,
+        });
 
         // If we've reached depth 1 in an illegal line, we need to find which move(s) are the problematic ones
         if ILLEGAL && depth == 1 {
@@ -244,9 +944,15 @@ impl<'a> PerftChecker<'a> {
             // Generate the FEN of the position after applying all of the problematic moves
             let new_fen = generate_fen_from(fen, moves);
 
-            // Format and return the error message
-            let moves_str = moves.join(", ");
-            bail!("{list_diff_err}\nApplied moves: {moves_str}\nResulting FEN: {new_fen:?}");
+            // Report and return the mismatch
+            return Err(Mismatch {
+                actual_nodes: user_nodes,
+                offending_move: None,
+                applied_moves: moves.to_vec(),
+                resulting_fen: new_fen,
+                reason: list_diff_err.to_string(),
+            }
+            .into());
         }
 
         // If the user-supplied script did not generate the proper number of nodes, there's an error we need to find
@@ -269,14 +975,22 @@ impl<'a> PerftChecker<'a> {
                     // Generate the FEN of the position after applying all of the problematic moves
                     let new_fen = generate_fen_from(fen, moves);
 
-                    // Format and return the error
-                    let moves_str = moves.join(", ");
+                    // Report and return the mismatch
                     let user_moves = user_splitperft
                         .into_iter()
                         .map(|(mv, _)| mv)
                         .collect::<Vec<_>>()
                         .join(" ");
-                    bail!("Failed to generate legal move {mv:?}\nApplied moves: {moves_str}\nResulting FEN: {new_fen:?}\nGenerated moves: {user_moves}");
+                    return Err(Mismatch {
+                        actual_nodes: user_nodes,
+                        offending_move: Some(mv.clone()),
+                        applied_moves: moves.to_vec(),
+                        resulting_fen: new_fen,
+                        reason: format!(
+                            "Failed to generate legal move {mv:?}\nGenerated moves: {user_moves}"
+                        ),
+                    }
+                    .into());
                 };
 
                 // If the user-supplied script generated an incorrect number of nodes after this move, then we need to follow this move until we reach the problematic position
@@ -288,13 +1002,206 @@ impl<'a> PerftChecker<'a> {
                     moves_to_inspect.push(mv);
 
                     // Recursively check the resulting position
-                    self.check_splitperft::<true>(depth - 1, fen, &moves_to_inspect)?;
+                    if let Err(mut err) =
+                        self.check_splitperft::<true>(depth - 1, fen, &moves_to_inspect, tt)
+                    {
+                        // Bisection reports the node count at whatever shallower position it bottomed
+                        // out at; overwrite it with this check's own top-level count as it propagates.
+                        if let Some(mismatch) = err.downcast_mut::<Mismatch>() {
+                            mismatch.actual_nodes = user_nodes;
+                        }
+                        return Err(err);
+                    }
                 }
             }
         }
 
-        Ok(())
+        Ok(CheckOutcome {
+            actual_nodes: user_nodes,
+            benchmark,
+        })
+    }
+}
+
+/// Computes `perft(depth)` for `game`, memoizing results in `tt` by `(zobrist hash, depth)`.
+///
+/// Performs bulk counting: at `depth == 1`, this returns the number of legal moves directly
+/// instead of making each move and recursing down to depth 0, since each legal move accounts
+/// for exactly one leaf node.
+fn perft_with_tt(game: &Game, depth: usize, tt: &mut PerftTable) -> u64 {
+    if depth == 1 {
+        return game.get_legal_moves().len() as u64;
+    } else if depth == 0 {
+        return 1;
+    }
+
+    let key = (game.key().inner(), depth);
+    if let Some(&nodes) = tt.get(&key) {
+        return nodes;
+    }
+
+    let nodes = game
+        .get_legal_moves()
+        .into_iter()
+        .fold(0, |nodes, mv| nodes + perft_with_tt(&game.with_move_made(mv), depth - 1, tt));
+
+    tt.insert(key, nodes);
+    nodes
+}
+
+/// Builds a single-depth perft test suite for `game`'s current position.
+fn perft_suite(game: &Game, depth: usize) -> Suite {
+    let nodes = perft_with_tt(game, depth, &mut PerftTable::new());
+    (game.to_fen(), vec![(depth, nodes)])
+}
+
+/// Parses a SAN (Standard Algebraic Notation) move string into the [`Move`] it refers to on
+/// `game`, matching it against [`Game::get_legal_moves`] rather than reconstructing the move
+/// from scratch (chessie's own `Move::from_san` is unfinished and unusable).
+fn move_from_san(game: &Game, san: &str) -> Result<Move> {
+    // Check/mate/annotation suffixes don't affect which move is meant.
+    let san = san.trim_end_matches(['+', '#', '!', '?']);
+
+    if san == "O-O" || san == "0-0" {
+        return game
+            .get_legal_moves()
+            .into_iter()
+            .find(|mv| mv.kind() == MoveKind::ShortCastle)
+            .ok_or_else(|| anyhow!("No legal short castle for {san:?} on {:?}", game.to_fen()));
+    }
+    if san == "O-O-O" || san == "0-0-0" {
+        return game
+            .get_legal_moves()
+            .into_iter()
+            .find(|mv| mv.kind() == MoveKind::LongCastle)
+            .ok_or_else(|| anyhow!("No legal long castle for {san:?} on {:?}", game.to_fen()));
+    }
+
+    // Strip a trailing promotion suffix, e.g. "e8=Q".
+    let (body, promotion) = match san.split_once('=') {
+        Some((body, promo)) => {
+            let promo_char = promo
+                .chars()
+                .next()
+                .context(format!("Missing promotion piece in SAN move {san:?}"))?;
+            (body, Some(PieceKind::from_uci(promo_char)?))
+        }
+        None => (san, None),
+    };
+
+    // A leading uppercase letter names the piece; Pawn moves have no such prefix.
+    let (piece_kind, rest) = match body.chars().next() {
+        Some(c) if c.is_ascii_uppercase() => (PieceKind::from_uci(c)?, &body[1..]),
+        _ => (PieceKind::Pawn, body),
+    };
+
+    // The "x" marking a capture carries no information we need once it's removed.
+    let rest = rest.replace('x', "");
+    if rest.len() < 2 {
+        bail!("Could not find a destination square in SAN move {san:?}");
+    }
+    let (disambiguation, to) = rest.split_at(rest.len() - 2);
+    let to: Square =
+        to.parse().context(format!("Invalid destination square in SAN move {san:?}"))?;
+
+    let mut candidates = game.get_legal_moves().into_iter().filter(|mv| {
+        mv.to() == to
+            && mv.promotion() == promotion
+            && game.piece_at(mv.from()).is_some_and(|p| p.kind() == piece_kind)
+            && (disambiguation.is_empty() || mv.from().to_string().contains(disambiguation))
+    });
+
+    let mv = candidates
+        .next()
+        .ok_or_else(|| anyhow!("No legal move matches SAN {san:?} on {:?}", game.to_fen()))?;
+
+    if candidates.next().is_some() {
+        bail!("SAN move {san:?} is ambiguous on {:?}", game.to_fen());
     }
+
+    Ok(mv)
+}
+
+/// Splits a PGN file's contents into per-game chunks (tag pairs and movetext together),
+/// splitting at each `[Event` tag after the first.
+fn split_pgn_games(contents: &str) -> Vec<String> {
+    let mut games = Vec::new();
+    let mut current = String::new();
+
+    for line in contents.lines() {
+        if line.trim_start().starts_with("[Event ") && !current.trim().is_empty() {
+            games.push(std::mem::take(&mut current));
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+
+    if !current.trim().is_empty() {
+        games.push(current);
+    }
+
+    games
+}
+
+/// Extracts the value of a `[Tag "value"]` pair from a PGN game's text, if present.
+fn extract_pgn_tag(game_text: &str, tag: &str) -> Option<String> {
+    let prefix = format!("[{tag} \"");
+    game_text
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(prefix.as_str())?.strip_suffix("\"]"))
+        .map(str::to_string)
+}
+
+/// Extracts the ordered list of mainline SAN move tokens from a PGN game's text, discarding
+/// tag pairs, comments, variations, move numbers, NAGs, and the trailing game result.
+fn mainline_sans(game_text: &str) -> Vec<String> {
+    let movetext = game_text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('['))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    strip_pgn_comments_and_variations(&movetext)
+        .split_ascii_whitespace()
+        .filter(|tok| !is_pgn_move_number(tok) && !is_pgn_nag(tok) && !is_pgn_result(tok))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Strips PGN comments (`{...}`) and variations (`(...)`, which may nest) from `movetext`.
+fn strip_pgn_comments_and_variations(movetext: &str) -> String {
+    let mut out = String::with_capacity(movetext.len());
+    let mut variation_depth = 0usize;
+    let mut in_comment = false;
+
+    for ch in movetext.chars() {
+        match ch {
+            '{' if !in_comment => in_comment = true,
+            '}' if in_comment => in_comment = false,
+            '(' if !in_comment => variation_depth += 1,
+            ')' if !in_comment && variation_depth > 0 => variation_depth -= 1,
+            _ if in_comment || variation_depth > 0 => {}
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+/// True for move-number tokens, like `"12."` or `"12..."`.
+fn is_pgn_move_number(tok: &str) -> bool {
+    let digits = tok.trim_end_matches('.');
+    tok.contains('.') && !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// True for Numeric Annotation Glyphs, like `"$1"`.
+fn is_pgn_nag(tok: &str) -> bool {
+    tok.starts_with('$')
+}
+
+/// True for a PGN game-termination marker.
+fn is_pgn_result(tok: &str) -> bool {
+    matches!(tok, "1-0" | "0-1" | "1/2-1/2" | "*")
 }
 
 /// Generates a FEN string after applying all of `moves` to the provided `fen`.
@@ -308,7 +1215,7 @@ fn generate_fen_from(fen: &str, moves: &[String]) -> String {
         board = board.with_move_made(mv);
     }
 
-    board.to_string()
+    board.to_fen()
 }
 
 /// Checks the contents of two lists.
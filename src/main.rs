@@ -4,7 +4,7 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use autoperft::PerftChecker;
+use autoperft::{OutputFormat, PerftChecker};
 use clap::Parser;
 
 /// Command-line tool for debugging chess move generation
@@ -29,27 +29,74 @@ struct Args {
     /// i.e. `--skip 10 --first 13` will run tests 10, 11, and 12.
     #[arg(short = 'f', long = "first", default_value = "128")]
     first: usize,
+
+    /// Time the user script against the reference generator and report nodes-per-second for both.
+    #[arg(short = 'b', long = "benchmark")]
+    benchmark: bool,
+
+    /// How to report results. `json` and `tap` are machine-readable, for CI integration.
+    #[arg(long = "format", value_enum, default_value_t = OutputFormat::Human)]
+    format: OutputFormat,
+
+    /// Don't stop at the first failing suite or malformed EPD line; check everything and
+    /// print an aggregated summary of every failure at the end.
+    #[arg(long = "keep-going")]
+    keep_going: bool,
+
+    /// Path to a PGN file to import positions from, instead of `--epd`.
+    ///
+    /// Every position visited while walking each game's mainline (including the starting
+    /// position) is checked with a single `perft(--pgn-depth)`.
+    #[arg(long = "pgn")]
+    pgn_file_path: Option<String>,
+
+    /// Depth at which to check each position imported via `--pgn`.
+    #[arg(long = "pgn-depth", default_value = "1")]
+    pgn_depth: usize,
+
+    /// Number of worker threads to check suites concurrently with, and to divide each reference
+    /// `perft`'s root-level moves across. `1` (the default) runs everything on a single thread.
+    #[arg(short = 'j', long = "jobs", default_value = "1")]
+    jobs: usize,
 }
 
 fn main() {
     let args = Args::parse();
 
-    let checker = PerftChecker::new(&args.movegen_script);
+    let checker = PerftChecker::new(
+        &args.movegen_script,
+        args.benchmark,
+        args.format,
+        args.keep_going,
+        args.jobs,
+    );
 
-    // Ensure indices are proper
-    if args.skip >= args.first {
-        println!(
-            "Argument for `--skip` ({}) must be strictly less than argument for `--first` ({})",
-            args.skip, args.first
-        );
-        std::process::exit(1);
-    }
+    // `--pgn` is a separate input source from `--epd`, so it skips the `--skip`/`--first` slicing
+    let result = if let Some(pgn_file_path) = &args.pgn_file_path {
+        checker.run_pgn(pgn_file_path, args.pgn_depth)
+    } else {
+        // Ensure indices are proper
+        if args.skip >= args.first {
+            println!(
+                "Argument for `--skip` ({}) must be strictly less than argument for `--first` ({})",
+                args.skip, args.first
+            );
+            std::process::exit(1);
+        }
 
-    // Run the checker on the test suite file
-    if let Err(e) = checker.run(&args.epd_file_path, args.skip, args.first) {
-        println!(
-            "\n{} failed with the following error:\n{e}",
-            env!("CARGO_PKG_NAME")
-        );
+        checker.run(&args.epd_file_path, args.skip, args.first)
+    };
+
+    if let Err(e) = result {
+        // Under `--format json`/`tap`, every check already went through a structured record; a
+        // trailing prose error would just be noise a parser can't do anything with. The process
+        // still has to exit non-zero, though, so a CI pipeline can tell the run failed.
+        if args.format == OutputFormat::Human {
+            println!(
+                "\n{} failed with the following error:\n{e}",
+                env!("CARGO_PKG_NAME")
+            );
+        }
+        std::process::exit(1);
     }
 }